@@ -1,129 +1,335 @@
+/*!
+Charlestown is a simple RFC 4180-compliant reader and writer for CSVs
+*/
+
 use std::collections::HashMap;
+use std::io::Read;
 
-/**!
+///The error type for every fallible operation in this crate. Carrying the offending
+///record/field or column name (rather than a bare `()`) lets callers tell a missing file
+///from malformed UTF-8 from an out-of-range index.
+#[derive(Debug)]
+pub enum CsvError {
+    ///An I/O failure while reading or writing a file.
+    Io(std::io::Error),
+    ///A cell's bytes were not valid UTF-8, at the given zero-indexed record and field.
+    Utf8 { record: usize, field: usize },
+    ///A quoted cell was never closed before the input ended, at the given zero-indexed record.
+    UnterminatedQuote { record: usize },
+    ///A row or cell index was out of bounds for the table.
+    RowIndexOutOfBounds,
+    ///The named column does not exist in the table's header.
+    ColumnNotFound(String),
+    ///A `serde` (de)serialization failure, carrying the underlying error's message.
+    #[cfg(feature = "serde")]
+    Serde(String),
+}
 
-Charlestown is a simple RFC 4180-compliant reader and writer for CSVs
+impl std::fmt::Display for CsvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {}", e),
+            CsvError::Utf8 { record, field } => {
+                write!(f, "invalid UTF-8 in record {}, field {}", record, field)
+            }
+            CsvError::UnterminatedQuote { record } => {
+                write!(f, "unterminated quote in record {}", record)
+            }
+            CsvError::RowIndexOutOfBounds => write!(f, "row or cell index out of bounds"),
+            CsvError::ColumnNotFound(column) => write!(f, "column not found: {}", column),
+            #[cfg(feature = "serde")]
+            CsvError::Serde(message) => write!(f, "serde error: {}", message),
+        }
+    }
+}
 
-*/
+impl std::error::Error for CsvError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CsvError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for CsvError {
+    fn from(e: std::io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
 
-enum BytestreamReaderResult {
-    LastOfLine(Vec<u8>),
-    NonTerminalCell(Vec<u8>),
+///Determines how a `CSVReaderBuilder`-configured reader recognizes the end of a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terminator {
+    ///Records are terminated by `\r\n`, with a bare `\n` also accepted, per RFC 4180.
+    CRLF,
+    ///Records are terminated by a single arbitrary byte.
+    Any(u8),
 }
 
-struct CSVReader {
-    bytes: Vec<u8>,
-    ptr: usize,
-    len: usize,
+///A streaming iterator over the rows of a CSV source, built from the quote/delimiter/
+///terminator state machine but fed one byte at a time from a buffered `Read` rather than
+///indexing a fully-loaded byte vector. This keeps memory bounded for large files.
+pub struct CSVRowIter<R: std::io::Read> {
+    bytes: std::iter::Peekable<std::io::Bytes<std::io::BufReader<R>>>,
+    delimiter: u8,
+    quote: u8,
+    terminator: Terminator,
+    trim: Trim,
+    in_quotes: bool,
+    current_cell: Vec<u8>,
+    current_row: Vec<String>,
+    record: usize,
+    field: usize,
+    exhausted: bool,
 }
 
-impl CSVReader {
-    fn eof(&self) -> bool {
-        self.ptr == self.len
+impl<R: std::io::Read> CSVRowIter<R> {
+    fn new(reader: R, delimiter: u8, quote: u8, terminator: Terminator, trim: Trim) -> Self {
+        Self {
+            bytes: std::io::BufReader::new(reader).bytes().peekable(),
+            delimiter,
+            quote,
+            terminator,
+            trim,
+            in_quotes: false,
+            current_cell: Vec::new(),
+            current_row: Vec::new(),
+            record: 0,
+            field: 0,
+            exhausted: false,
+        }
     }
 
-    fn pop(&mut self) -> Result<u8, ()> {
-        if self.eof() {
-            Err(())
-        } else {
-            let output = self.bytes[self.ptr];
-            self.ptr += 1;
-            Ok(output)
+    ///Whether the cell at the current record should be trimmed, per this iterator's `Trim`
+    ///mode (`Headers`/`Fields` key off whether `record` is the first row).
+    fn should_trim(&self, record: usize) -> bool {
+        match self.trim {
+            Trim::None => false,
+            Trim::All => true,
+            Trim::Headers => record == 0,
+            Trim::Fields => record != 0,
         }
     }
 
-    fn peek(&self) -> Result<u8, ()> {
-        if self.eof() {
-            Err(())
-        } else {
-            Ok(self.bytes[self.ptr])
+    fn finish_cell(&mut self) -> Result<(), CsvError> {
+        let cell = std::mem::take(&mut self.current_cell);
+        let field = self.field;
+        self.field += 1;
+        let record = self.record;
+        let mut cell =
+            String::from_utf8(cell).map_err(|_| CsvError::Utf8 { record, field })?;
+        if self.should_trim(record) {
+            cell = cell.trim().to_owned();
         }
+        self.current_row.push(cell);
+        Ok(())
     }
 
-    fn from_vec(input: Vec<u8>) -> Self {
-        let len = input.len();
-        Self {
-            bytes: input,
-            ptr: 0,
-            len: len,
-        }
-    }
-
-    fn to_bytestream_reader_results(&mut self) -> Vec<BytestreamReaderResult> {
-        let mut output = Vec::<BytestreamReaderResult>::new();
-        let mut current_cell = Vec::<u8>::new();
-        let mut in_quotes = false;
-        while !self.eof() {
-            match self.pop().unwrap() {
-                0x22 => {
-                    if in_quotes {
-                        if self.peek() == Ok(0x22) {
-                            current_cell.push(0x22);
-                            self.pop().unwrap();
-                        } else {
-                            in_quotes = false;
-                        }
-                    } else {
-                        in_quotes = true;
+    fn finish_row(&mut self) -> Option<Result<Vec<String>, CsvError>> {
+        let result = match self.finish_cell() {
+            Ok(()) => Some(Ok(std::mem::take(&mut self.current_row))),
+            Err(e) => Some(Err(e)),
+        };
+        self.record += 1;
+        self.field = 0;
+        result
+    }
+
+    fn peek_is(&mut self, byte: u8) -> bool {
+        matches!(self.bytes.peek(), Some(Ok(b)) if *b == byte)
+    }
+}
+
+impl<R: std::io::Read> Iterator for CSVRowIter<R> {
+    type Item = Result<Vec<String>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+        loop {
+            let byte = match self.bytes.next() {
+                None => {
+                    self.exhausted = true;
+                    if self.in_quotes {
+                        return Some(Err(CsvError::UnterminatedQuote { record: self.record }));
                     }
-                }
-                0x2C => {
-                    if in_quotes {
-                        current_cell.push(0x2C);
-                    } else {
-                        output.push(BytestreamReaderResult::NonTerminalCell(
-                            current_cell.clone(),
-                        ));
-                        current_cell.clear();
+                    if self.current_cell.is_empty() && self.current_row.is_empty() {
+                        return None;
                     }
+                    return self.finish_row();
                 }
-                0x0D => {
-                    if in_quotes {
-                        current_cell.push(0x0D);
-                    } else if self.peek() == Ok(0x0A) {
-                        self.pop().unwrap();
-                        output.push(BytestreamReaderResult::LastOfLine(current_cell.clone()));
-                        current_cell.clear();
-                    } else {
-                        current_cell.push(0x0D);
-                    }
+                Some(Err(e)) => {
+                    self.exhausted = true;
+                    return Some(Err(CsvError::Io(e)));
                 }
-                0x0A => {
-                    if in_quotes {
-                        current_cell.push(0x0A);
+                Some(Ok(byte)) => byte,
+            };
+            if byte == self.quote {
+                if self.in_quotes {
+                    if self.peek_is(self.quote) {
+                        self.current_cell.push(self.quote);
+                        self.bytes.next();
                     } else {
-                        output.push(BytestreamReaderResult::LastOfLine(current_cell.clone()));
-                        current_cell.clear();
+                        self.in_quotes = false;
                     }
+                } else {
+                    self.in_quotes = true;
+                }
+            } else if byte == self.delimiter {
+                if self.in_quotes {
+                    self.current_cell.push(self.delimiter);
+                } else if let Err(e) = self.finish_cell() {
+                    return Some(Err(e));
+                }
+            } else if self.terminator == Terminator::CRLF && byte == 0x0D {
+                if self.in_quotes {
+                    self.current_cell.push(0x0D);
+                } else if self.peek_is(0x0A) {
+                    self.bytes.next();
+                    return self.finish_row();
+                } else {
+                    self.current_cell.push(0x0D);
                 }
-                r => {
-                    current_cell.push(r);
+            } else if self.terminator == Terminator::CRLF && byte == 0x0A {
+                if self.in_quotes {
+                    self.current_cell.push(0x0A);
+                } else {
+                    return self.finish_row();
                 }
+            } else if self.terminator == Terminator::Any(byte) && !self.in_quotes {
+                return self.finish_row();
+            } else {
+                self.current_cell.push(byte);
             }
         }
-        if !current_cell.is_empty() {
-            output.push(BytestreamReaderResult::LastOfLine(current_cell));
+    }
+}
+
+///Determines which cells have leading/trailing whitespace stripped.
+///
+///Defaults to `None` to preserve RFC 4180 fidelity, since trimming unconditionally
+///corrupts data whose whitespace is significant and makes quoted `" a "` indistinguishable
+///from `a`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trim {
+    ///No cells are trimmed.
+    None,
+    ///Only the first row (the header row, once consumed by `HeaderedCSVTable`) is trimmed.
+    Headers,
+    ///Every row except the first is trimmed.
+    Fields,
+    ///Every cell, header or field, is trimmed.
+    All,
+}
+
+///Configures a `CSVRowIter` before producing an `UnheaderedCSVTable`, mirroring the
+///configurability of the `csv` crate's `ReaderBuilder`. This allows callers to parse
+///TSVs, pipe-delimited files, and other CSV dialects without pre-processing.
+pub struct CSVReaderBuilder {
+    delimiter: u8,
+    quote: u8,
+    terminator: Terminator,
+    flexible: bool,
+    trim: Trim,
+}
+
+impl Default for CSVReaderBuilder {
+    fn default() -> Self {
+        Self {
+            delimiter: 0x2C,
+            quote: 0x22,
+            terminator: Terminator::CRLF,
+            flexible: true,
+            trim: Trim::None,
         }
-        output
     }
+}
 
-    fn to_unheadered_csv_input_table_contents(&mut self) -> Vec<Vec<String>> {
-        let bytestream_reader_results = self.to_bytestream_reader_results();
-        let mut table: Vec<Vec<String>> = Vec::new();
-        let mut current_row = Vec::<String>::new();
-        for bsr_i in bytestream_reader_results {
-            match bsr_i {
-                BytestreamReaderResult::NonTerminalCell(ntc) => {
-                    current_row.push(String::from_utf8(ntc).unwrap().trim().to_owned());
-                }
-                BytestreamReaderResult::LastOfLine(ntc) => {
-                    current_row.push(String::from_utf8(ntc).unwrap().trim().to_owned());
-                    table.push(current_row.clone());
-                    current_row.clear();
-                }
+impl CSVReaderBuilder {
+    ///Creates a new builder with the default RFC 4180 settings: comma-delimited,
+    ///double-quoted, CRLF/LF terminated, and flexible.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Sets the byte used to separate cells within a record. Defaults to `,` (0x2C).
+    pub fn delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    ///Sets the byte used to quote cells. Defaults to `"` (0x22).
+    pub fn quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    ///Sets the record terminator. Defaults to `Terminator::CRLF`.
+    pub fn terminator(mut self, terminator: Terminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    ///Sets whether rows are allowed to have a different number of cells than the first
+    ///row. Defaults to `true`.
+    ///
+    ///Note this differs from the `csv` crate's `ReaderBuilder::flexible`, which rejects
+    ///ragged records with an error when set to `false`: here, `false` instead pads rows
+    ///shorter than the first row with empty cells, and leaves rows longer than the first
+    ///row untouched rather than erroring.
+    pub fn flexible(mut self, flexible: bool) -> Self {
+        self.flexible = flexible;
+        self
+    }
+
+    ///Sets which cells are trimmed of leading/trailing whitespace. Defaults to `Trim::None`.
+    pub fn trim(mut self, trim: Trim) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    fn balance(&self, mut rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+        if self.flexible || rows.is_empty() {
+            return rows;
+        }
+        let width = rows[0].len();
+        for row in rows.iter_mut() {
+            while row.len() < width {
+                row.push(String::new());
             }
         }
-        table
+        rows
+    }
+
+    ///Returns a streaming iterator over the rows produced by this builder's configuration
+    ///(including its `Trim` mode), reading incrementally from `reader` rather than loading
+    ///it into memory all at once.
+    pub fn from_reader<R: std::io::Read>(&self, reader: R) -> CSVRowIter<R> {
+        CSVRowIter::new(reader, self.delimiter, self.quote, self.terminator, self.trim)
+    }
+
+    ///Turns a byte vector (like the contents of a CSV file) into an UnheaderedCSVTable
+    ///instance, using this builder's configuration.
+    pub fn from_byte_vector(&self, input: Vec<u8>) -> Result<UnheaderedCSVTable, CsvError> {
+        let rows = self
+            .from_reader(std::io::Cursor::new(input))
+            .collect::<Result<Vec<Vec<String>>, CsvError>>()?;
+        Ok(UnheaderedCSVTable::from_rows(self.balance(rows)))
+    }
+
+    ///Turns a string (like the contents of a CSV file) into an UnheaderedCSVTable instance,
+    ///using this builder's configuration.
+    pub fn from_string(&self, input: &str) -> Result<UnheaderedCSVTable, CsvError> {
+        self.from_byte_vector(input.as_bytes().to_vec())
+    }
+
+    ///Reads an UnheaderedCSVTable from a file, using this builder's configuration. Note
+    ///that this can be called on headered CSV files, but the first row will be assumed to
+    ///contain a record.
+    pub fn from_file_location(&self, path: &str) -> Result<UnheaderedCSVTable, CsvError> {
+        self.from_byte_vector(std::fs::read(path)?)
     }
 }
 
@@ -143,9 +349,9 @@ impl UnheaderedCSVTable {
     }
 
     ///Gets a row (as a result of a string vector)
-    pub fn get_row(&self, row_index: usize) -> Result<Vec<String>, ()> {
+    pub fn get_row(&self, row_index: usize) -> Result<Vec<String>, CsvError> {
         match self.0.get(row_index) {
-            None => Err(()),
+            None => Err(CsvError::RowIndexOutOfBounds),
             Some(r) => Ok(r.clone()),
         }
     }
@@ -155,26 +361,29 @@ impl UnheaderedCSVTable {
         self.0.len()
     }
 
+    ///Returns whether the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     ///Returns all members of a column as a vector of string results
-    pub fn get_column(&self, input: usize) -> Vec<Result<String, ()>> {
+    pub fn get_column(&self, input: usize) -> Vec<Result<String, CsvError>> {
         self.0
             .clone()
             .into_iter()
             .map(|x| match x.get(input) {
-                None => Err(()),
+                None => Err(CsvError::RowIndexOutOfBounds),
                 Some(r) => Ok(r.clone()),
             })
-            .collect::<Vec<Result<String, ()>>>()
+            .collect::<Vec<Result<String, CsvError>>>()
     }
 
     ///Returns the contents of an indexed cell as a string result
-    pub fn get_cell(&self, row: usize, column: usize) -> Result<String, ()> {
-        match self.get_row(row) {
-            Err(()) => Err(()),
-            Ok(row) => match row.get(column) {
-                None => Err(()),
-                Some(r) => Ok(r.clone()),
-            },
+    pub fn get_cell(&self, row: usize, column: usize) -> Result<String, CsvError> {
+        let row = self.get_row(row)?;
+        match row.get(column) {
+            None => Err(CsvError::RowIndexOutOfBounds),
+            Some(r) => Ok(r.clone()),
         }
     }
 
@@ -193,7 +402,7 @@ impl UnheaderedCSVTable {
             .into_iter()
             .map(|x| {
                 x.into_iter()
-                    .map(|y| Self::csv_sanitize(y))
+                    .map(Self::csv_sanitize)
                     .collect::<Vec<String>>()
                     .join(",")
             })
@@ -202,32 +411,160 @@ impl UnheaderedCSVTable {
     }
 
     ///Saves this table to a CSV file
-    pub fn save(&self, path: String) -> Result<(), ()> {
-        match std::fs::write(path, self.stringify()) {
-            Ok(()) => Ok(()),
-            Err(_) => Err(()),
-        }
+    pub fn save(&self, path: String) -> Result<(), CsvError> {
+        std::fs::write(path, self.stringify())?;
+        Ok(())
     }
 
     ///Turns a byte vector (like the contents of a CSV file) into an UnheaderedCSVTable instance
-    pub fn from_byte_vector(input: Vec<u8>) -> Self {
-        Self(CSVReader::from_vec(input).to_unheadered_csv_input_table_contents())
+    pub fn from_byte_vector(input: Vec<u8>) -> Result<Self, CsvError> {
+        CSVReaderBuilder::default().from_byte_vector(input)
     }
 
     ///Turns a string (like the contents of a CSV file) into an UnheaderedCSVTable instance
-    pub fn from_string(input: &str) -> UnheaderedCSVTable {
-        Self(
-            CSVReader::from_vec(input.as_bytes().to_vec()).to_unheadered_csv_input_table_contents(),
-        )
+    pub fn from_string(input: &str) -> Result<UnheaderedCSVTable, CsvError> {
+        CSVReaderBuilder::default().from_string(input)
     }
 
     ///Reads an UnheaderedCSVTable from a file. Note that this can be called on headered CSV
     ///files, but the first row will be assumed to contain a record.
-    pub fn from_file_location(path: &str) -> Result<UnheaderedCSVTable, ()> {
-        match std::fs::read_to_string(path) {
-            Err(_) => Err(()),
-            Ok(string) => Ok(Self::from_byte_vector(string.as_bytes().to_vec())),
+    pub fn from_file_location(path: &str) -> Result<UnheaderedCSVTable, CsvError> {
+        CSVReaderBuilder::default().from_file_location(path)
+    }
+}
+
+///How two tables are combined by `HeaderedCSVTable::join`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JoinKind {
+    ///Only rows with a matching key in both tables are emitted.
+    Inner,
+    ///Every left row is emitted; rows with no match are padded with empty right columns.
+    Left,
+    ///Every right row is emitted; rows with no match are padded with empty left columns.
+    Right,
+    ///Every left and right row is emitted, padding whichever side has no match.
+    Full,
+    ///Every combination of a left row and a right row is emitted; the keys are ignored.
+    Cross,
+}
+
+///Min/max/mean for a column whose non-empty cells all parse as `f64`. See
+///`HeaderedCSVTable::describe`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumericSummary {
+    ///The smallest value in the column.
+    pub min: f64,
+    ///The largest value in the column.
+    pub max: f64,
+    ///The arithmetic mean of the column's values.
+    pub mean: f64,
+}
+
+///Per-column profile produced by `HeaderedCSVTable::describe`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnSummary {
+    ///The column name.
+    pub column: String,
+    ///The number of cells in this column, which is the number of rows in the table. A header
+    ///slot whose name was collapsed by a duplicate is reported under an empty name with a
+    ///count of 0.
+    pub count: usize,
+    ///The number of cells in this column that are the empty string.
+    pub empty: usize,
+    ///The number of distinct values in this column.
+    pub distinct: usize,
+    ///Min/max/mean, present only when every non-empty cell parses as an `f64`.
+    pub numeric: Option<NumericSummary>,
+}
+
+///The error produced while coercing a CSV cell's string into a typed struct field.
+///Converted into a `CsvError::Serde` before it reaches callers of `deserialize_row`.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+struct CellDeserializeError(String);
+
+#[cfg(feature = "serde")]
+impl std::fmt::Display for CellDeserializeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for CellDeserializeError {}
+
+#[cfg(feature = "serde")]
+impl serde::de::Error for CellDeserializeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+///Deserializes a single CSV cell, coercing its string contents into whatever type the
+///target struct field asks for (mirroring the way the `csv` crate deserializes a
+///`StringRecord`).
+#[cfg(feature = "serde")]
+struct CellDeserializer(String);
+
+#[cfg(feature = "serde")]
+macro_rules! deserialize_parsed_cell {
+    ($method:ident, $visit:ident, $ty:ty) => {
+        fn $method<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            let parsed = self.0.parse::<$ty>().map_err(|e| {
+                CellDeserializeError(format!(
+                    "cannot parse \"{}\" as {}: {}",
+                    self.0,
+                    stringify!($ty),
+                    e
+                ))
+            })?;
+            visitor.$visit(parsed)
         }
+    };
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Deserializer<'de> for CellDeserializer {
+    type Error = CellDeserializeError;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_option<V: serde::de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    deserialize_parsed_cell!(deserialize_bool, visit_bool, bool);
+    deserialize_parsed_cell!(deserialize_i8, visit_i8, i8);
+    deserialize_parsed_cell!(deserialize_i16, visit_i16, i16);
+    deserialize_parsed_cell!(deserialize_i32, visit_i32, i32);
+    deserialize_parsed_cell!(deserialize_i64, visit_i64, i64);
+    deserialize_parsed_cell!(deserialize_u8, visit_u8, u8);
+    deserialize_parsed_cell!(deserialize_u16, visit_u16, u16);
+    deserialize_parsed_cell!(deserialize_u32, visit_u32, u32);
+    deserialize_parsed_cell!(deserialize_u64, visit_u64, u64);
+    deserialize_parsed_cell!(deserialize_f32, visit_f32, f32);
+    deserialize_parsed_cell!(deserialize_f64, visit_f64, f64);
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any i128 u128
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::IntoDeserializer<'de, CellDeserializeError> for CellDeserializer {
+    type Deserializer = Self;
+    fn into_deserializer(self) -> Self {
+        self
     }
 }
 
@@ -242,7 +579,7 @@ impl HeaderedCSVTable {
     ///Creates a HeaderedCSVTable from an UnheaderedCSVTable by assuming the first column is a header.
     ///Note that this may add extra cells and columns to balance the table.
     pub fn from_unheadered_csv_table(input: UnheaderedCSVTable) -> Self {
-        let mut top_row = input.get_row(0).unwrap_or(Vec::new());
+        let mut top_row = input.get_row(0).unwrap_or_default();
         let mut max_rows_size = top_row.len();
         for i in 1..input.len() {
             if input.get_row(i).unwrap().len() > max_rows_size {
@@ -262,17 +599,17 @@ impl HeaderedCSVTable {
         }
         let mut columns = HashMap::<String, usize>::new();
         let top_row_string_vector = top_row;
-        for i in 0..top_row_string_vector.len() {
-            columns.insert(top_row_string_vector[i].clone(), i);
+        for (i, name) in top_row_string_vector.iter().enumerate() {
+            columns.insert(name.clone(), i);
         }
         Self { columns, rows }
     }
 
     ///Gets an indexed row as a vector of strings. Note that index 0 would refer to the second
     ///row of the CSV file, as the first row is now assumed to be a header
-    pub fn get_unheadered_row(&self, input: usize) -> Result<Vec<String>, ()> {
+    pub fn get_unheadered_row(&self, input: usize) -> Result<Vec<String>, CsvError> {
         match self.rows.get(input) {
-            None => Err(()),
+            None => Err(CsvError::RowIndexOutOfBounds),
             Some(r) => Ok(r.clone()),
         }
     }
@@ -280,16 +617,14 @@ impl HeaderedCSVTable {
     ///Gets an indexed row as a HashMap, where cells are accessed by their column header value.
     ///Note that index 0 would refer to the second
     ///row of the CSV file, as the first row is now assumed to be a header
-    pub fn get_headered_row(&self, input: usize) -> Result<HashMap<String, String>, ()> {
-        match self.get_unheadered_row(input) {
-            Err(()) => Err(()),
-            Ok(unheadered_row) => Ok(self
-                .columns
-                .clone()
-                .into_iter()
-                .map(|(x, y)| (x, unheadered_row.get(y).unwrap().clone()))
-                .collect::<HashMap<String, String>>()),
-        }
+    pub fn get_headered_row(&self, input: usize) -> Result<HashMap<String, String>, CsvError> {
+        let unheadered_row = self.get_unheadered_row(input)?;
+        Ok(self
+            .columns
+            .clone()
+            .into_iter()
+            .map(|(x, y)| (x, unheadered_row.get(y).unwrap().clone()))
+            .collect::<HashMap<String, String>>())
     }
 
     ///Gets the number of rows in the table
@@ -304,15 +639,15 @@ impl HeaderedCSVTable {
     }
 
     ///Gets the cells within a column as a vector of String results. If a column is called which
-    ///does not exist, an array of Err(()) values will be returned with the same magnitude as
-    ///the body of the table.
-    pub fn get_column(&self, input: &str) -> Vec<Result<String, ()>> {
+    ///does not exist, an array of `Err(CsvError::ColumnNotFound)` values will be returned with
+    ///the same magnitude as the body of the table.
+    pub fn get_column(&self, input: &str) -> Vec<Result<String, CsvError>> {
         let input_index = self.columns.get(input);
         match input_index {
             None => {
-                let mut v = Vec::<Result<String, ()>>::new();
+                let mut v = Vec::<Result<String, CsvError>>::new();
                 for _ in 0..self.rows.len() {
-                    v.push(Err(()));
+                    v.push(Err(CsvError::ColumnNotFound(input.to_owned())));
                 }
                 v
             }
@@ -321,25 +656,221 @@ impl HeaderedCSVTable {
                 .clone()
                 .into_iter()
                 .map(|x| match x.get(*input_index) {
-                    None => Err(()),
+                    None => Err(CsvError::RowIndexOutOfBounds),
                     Some(r) => Ok(r.clone()),
                 })
-                .collect::<Vec<Result<String, ()>>>(),
+                .collect::<Vec<Result<String, CsvError>>>(),
         }
     }
 
     ///Returns the value of a cell as a string result
-    pub fn get_cell(&self, row: usize, column: &str) -> Result<String, ()> {
-        match self.columns.get(column) {
-            None => Err(()),
-            Some(column_index) => match self.get_unheadered_row(row) {
-                Err(()) => Err(()),
-                Ok(row) => match row.get(*column_index) {
-                    None => Err(()),
-                    Some(r) => Ok(r.clone()),
-                },
-            },
+    pub fn get_cell(&self, row: usize, column: &str) -> Result<String, CsvError> {
+        let column_index = self
+            .columns
+            .get(column)
+            .ok_or_else(|| CsvError::ColumnNotFound(column.to_owned()))?;
+        let row = self.get_unheadered_row(row)?;
+        match row.get(*column_index) {
+            None => Err(CsvError::RowIndexOutOfBounds),
+            Some(r) => Ok(r.clone()),
+        }
+    }
+
+    ///Reconstructs the header row in column order. Duplicate header names are legal CSV and
+    ///collapse entries in `columns` (a `HashMap`), so the row width is taken from an actual
+    ///row rather than `columns.len()` to avoid indexing past a shrunken map (an empty table
+    ///uses the largest column index plus one); any index that still falls outside that width
+    ///is skipped rather than panicking. A slot whose name was collapsed is left empty.
+    fn ordered_headers(&self) -> Vec<String> {
+        let width = self
+            .rows
+            .first()
+            .map(|row| row.len())
+            .unwrap_or_else(|| self.columns.values().map(|&index| index + 1).max().unwrap_or(0));
+        let mut headers = vec![String::new(); width];
+        for (name, &index) in &self.columns {
+            if index < headers.len() {
+                headers[index] = name.clone();
+            }
+        }
+        headers
+    }
+
+    fn disambiguate_headers(left: &[String], right: &[String]) -> Vec<String> {
+        let mut headers = Vec::with_capacity(left.len() + right.len());
+        for name in left {
+            if right.contains(name) {
+                headers.push(format!("{}_1", name));
+            } else {
+                headers.push(name.clone());
+            }
+        }
+        for name in right {
+            if left.contains(name) {
+                headers.push(format!("{}_2", name));
+            } else {
+                headers.push(name.clone());
+            }
+        }
+        headers
+    }
+
+    ///Guarantees global uniqueness of a header row, in case `disambiguate_headers` still
+    ///leaves a collision (e.g. one side's own header row already had a repeated name): later
+    ///occurrences of a name get a numbered suffix. Without this, building `columns` from the
+    ///row would silently collapse a duplicate, leaving `columns.len()` short of the row's
+    ///actual width.
+    fn dedupe_headers(names: Vec<String>) -> Vec<String> {
+        let mut seen = HashMap::<String, usize>::new();
+        names
+            .into_iter()
+            .map(|name| {
+                let count = seen.entry(name.clone()).or_insert(0);
+                *count += 1;
+                if *count == 1 {
+                    name
+                } else {
+                    format!("{}_{}", name, count)
+                }
+            })
+            .collect()
+    }
+
+    fn concat_row(left: &[String], right: &[String]) -> Vec<String> {
+        let mut row = Vec::with_capacity(left.len() + right.len());
+        row.extend_from_slice(left);
+        row.extend_from_slice(right);
+        row
+    }
+
+    ///Joins this table with `other` on `left_key`/`right_key`, producing a new
+    ///HeaderedCSVTable whose columns are the concatenation of both headers (a name that
+    ///appears in both is disambiguated by suffixing `_1`/`_2`). Implemented as a hash join:
+    ///`other` is indexed once by `right_key` into a `HashMap<String, Vec<usize>>`, then each
+    ///row of `self` is looked up by `left_key` against that index.
+    pub fn join(
+        &self,
+        other: &HeaderedCSVTable,
+        left_key: &str,
+        right_key: &str,
+        kind: JoinKind,
+    ) -> HeaderedCSVTable {
+        let left_headers = self.ordered_headers();
+        let right_headers = other.ordered_headers();
+        let header_row =
+            Self::dedupe_headers(Self::disambiguate_headers(&left_headers, &right_headers));
+        let empty_left = vec![String::new(); left_headers.len()];
+        let empty_right = vec![String::new(); right_headers.len()];
+
+        let mut right_index = HashMap::<String, Vec<usize>>::new();
+        if kind != JoinKind::Cross {
+            if let Some(&key_index) = other.columns.get(right_key) {
+                for (i, row) in other.rows.iter().enumerate() {
+                    if let Some(value) = row.get(key_index) {
+                        right_index.entry(value.clone()).or_default().push(i);
+                    }
+                }
+            }
+        }
+
+        let mut matched_right = vec![false; other.rows.len()];
+        let mut rows = Vec::<Vec<String>>::new();
+
+        for left_row in &self.rows {
+            if kind == JoinKind::Cross {
+                for right_row in &other.rows {
+                    rows.push(Self::concat_row(left_row, right_row));
+                }
+                continue;
+            }
+            let matches = self
+                .columns
+                .get(left_key)
+                .and_then(|&idx| left_row.get(idx))
+                .and_then(|value| right_index.get(value));
+            match matches {
+                None => {
+                    if matches!(kind, JoinKind::Left | JoinKind::Full) {
+                        rows.push(Self::concat_row(left_row, &empty_right));
+                    }
+                }
+                Some(matches) => {
+                    for &right_i in matches {
+                        matched_right[right_i] = true;
+                        rows.push(Self::concat_row(left_row, &other.rows[right_i]));
+                    }
+                }
+            }
+        }
+
+        if matches!(kind, JoinKind::Right | JoinKind::Full) {
+            for (i, right_row) in other.rows.iter().enumerate() {
+                if !matched_right[i] {
+                    rows.push(Self::concat_row(&empty_left, right_row));
+                }
+            }
+        }
+
+        let mut columns = HashMap::<String, usize>::new();
+        for (i, name) in header_row.into_iter().enumerate() {
+            columns.insert(name, i);
         }
+        HeaderedCSVTable { columns, rows }
+    }
+
+    ///Tallies how many times each distinct value appears in `column`, sorted by descending
+    ///count (ties broken lexicographically by value), truncated to `limit` entries if given.
+    pub fn frequencies(&self, column: &str, limit: Option<usize>) -> Vec<(String, usize)> {
+        let mut counts = HashMap::<String, usize>::new();
+        for cell in self.get_column(column).into_iter().flatten() {
+            *counts.entry(cell).or_insert(0) += 1;
+        }
+        let mut counted = counts.into_iter().collect::<Vec<(String, usize)>>();
+        counted.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        if let Some(limit) = limit {
+            counted.truncate(limit);
+        }
+        counted
+    }
+
+    ///Profiles every column in this table: its cell count, number of empty cells, number of
+    ///distinct values, and — when every non-empty cell parses as `f64` — its min/max/mean.
+    pub fn describe(&self) -> Vec<ColumnSummary> {
+        self.ordered_headers()
+            .into_iter()
+            .map(|column| {
+                let cells = self
+                    .get_column(&column)
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<String>>();
+                let count = cells.len();
+                let empty = cells.iter().filter(|cell| cell.is_empty()).count();
+                let distinct = cells
+                    .iter()
+                    .collect::<std::collections::HashSet<&String>>()
+                    .len();
+                let numeric = cells
+                    .iter()
+                    .filter(|cell| !cell.is_empty())
+                    .map(|cell| cell.parse::<f64>().ok())
+                    .collect::<Option<Vec<f64>>>()
+                    .filter(|values| !values.is_empty())
+                    .map(|values| {
+                        let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+                        let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+                        let mean = values.iter().sum::<f64>() / values.len() as f64;
+                        NumericSummary { min, max, mean }
+                    });
+                ColumnSummary {
+                    column,
+                    count,
+                    empty,
+                    distinct,
+                    numeric,
+                }
+            })
+            .collect()
     }
 
     ///Turns this table into an unheadered table. Note that this is not the exact inverse of
@@ -367,28 +898,282 @@ impl HeaderedCSVTable {
     }
 
     ///Saves this to a CSV file
-    pub fn save(&self, path: String) -> Result<(), ()> {
-        match std::fs::write(path, self.stringify()) {
-            Ok(()) => Ok(()),
-            Err(_) => Err(()),
-        }
+    pub fn save(&self, path: String) -> Result<(), CsvError> {
+        std::fs::write(path, self.stringify())?;
+        Ok(())
     }
 
     ///Turns a byte vector (like the contents of a CSV file) into a HeaderedCSVTable instance
-    pub fn from_byte_vector(input: Vec<u8>) -> Self {
-        Self::from_unheadered_csv_table(UnheaderedCSVTable::from_byte_vector(input))
+    pub fn from_byte_vector(input: Vec<u8>) -> Result<Self, CsvError> {
+        Ok(Self::from_unheadered_csv_table(
+            UnheaderedCSVTable::from_byte_vector(input)?,
+        ))
     }
 
     ///Turns a string (like the contents of a CSV file) into a HeaderedCSVTable instance
-    pub fn from_string(input: &str) -> Self {
-        Self::from_unheadered_csv_table(UnheaderedCSVTable::from_string(input))
+    pub fn from_string(input: &str) -> Result<Self, CsvError> {
+        Ok(Self::from_unheadered_csv_table(
+            UnheaderedCSVTable::from_string(input)?,
+        ))
     }
 
     ///Reads an HeaderedCSV from a file. The first row will be assumed to contain the header.
-    pub fn from_file_location(path: &str) -> Result<Self, ()> {
-        match std::fs::read_to_string(path) {
-            Err(_) => Err(()),
-            Ok(string) => Ok(Self::from_byte_vector(string.as_bytes().to_vec())),
+    pub fn from_file_location(path: &str) -> Result<Self, CsvError> {
+        Self::from_byte_vector(std::fs::read(path)?)
+    }
+
+    ///Deserializes row `row` into `T` by mapping column headers to struct fields, the way
+    ///the `csv` crate and MeiliSearch's CSV document ingestion do, instead of hand-indexing
+    ///strings via `get_cell`/`get_headered_row`.
+    #[cfg(feature = "serde")]
+    pub fn deserialize_row<T: serde::de::DeserializeOwned>(&self, row: usize) -> Result<T, CsvError> {
+        let record = self.get_headered_row(row)?;
+        let fields = record.into_iter().map(|(k, v)| (k, CellDeserializer(v)));
+        let deserializer =
+            serde::de::value::MapDeserializer::<_, CellDeserializeError>::new(fields);
+        T::deserialize(deserializer).map_err(|e| CsvError::Serde(e.to_string()))
+    }
+
+    ///Builds a HeaderedCSVTable from `records`, reflecting each struct's field names into
+    ///the header row.
+    #[cfg(feature = "serde")]
+    pub fn from_records<T: serde::Serialize>(records: &[T]) -> Result<Self, CsvError> {
+        let mut header: Option<Vec<String>> = None;
+        let mut rows = Vec::with_capacity(records.len());
+        for record in records {
+            let value =
+                serde_json::to_value(record).map_err(|e| CsvError::Serde(e.to_string()))?;
+            let object = value
+                .as_object()
+                .ok_or_else(|| CsvError::Serde("expected a struct value".to_owned()))?;
+            let keys = header.get_or_insert_with(|| object.keys().cloned().collect::<Vec<String>>());
+            rows.push(
+                keys.iter()
+                    .map(|key| match object.get(key) {
+                        Some(serde_json::Value::String(s)) => s.clone(),
+                        Some(serde_json::Value::Null) | None => String::new(),
+                        Some(other) => other.to_string(),
+                    })
+                    .collect::<Vec<String>>(),
+            );
         }
+        let mut all_rows = vec![header.unwrap_or_default()];
+        all_rows.extend(rows);
+        Ok(Self::from_unheadered_csv_table(UnheaderedCSVTable::from_rows(all_rows)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaming_reader_yields_final_row_without_trailing_terminator() {
+        let rows = CSVReaderBuilder::new()
+            .from_reader(std::io::Cursor::new(b"a,b\r\nc,d".to_vec()))
+            .collect::<Result<Vec<Vec<String>>, CsvError>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                vec!["a".to_owned(), "b".to_owned()],
+                vec!["c".to_owned(), "d".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn streaming_reader_reports_unterminated_quote_with_its_record_index() {
+        let mut rows = CSVReaderBuilder::new()
+            .from_reader(std::io::Cursor::new(b"a,b\r\nc,\"d".to_vec()));
+        assert!(rows.next().unwrap().is_ok());
+        match rows.next() {
+            Some(Err(CsvError::UnterminatedQuote { record })) => assert_eq!(record, 1),
+            other => panic!("expected UnterminatedQuote at record 1, got {:?}", other),
+        }
+    }
+
+    fn sorted_rows(table: &HeaderedCSVTable) -> Vec<Vec<String>> {
+        let mut rows = (0..table.number_of_rows())
+            .map(|i| table.get_unheadered_row(i).unwrap())
+            .collect::<Vec<Vec<String>>>();
+        rows.sort();
+        rows
+    }
+
+    #[test]
+    fn join_inner_emits_only_matching_rows() {
+        let left = HeaderedCSVTable::from_string("id,name\r\n1,alice\r\n2,bob\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("id,city\r\n1,nyc\r\n3,la\r\n").unwrap();
+        let joined = left.join(&right, "id", "id", JoinKind::Inner);
+        assert_eq!(
+            sorted_rows(&joined),
+            vec![vec![
+                "1".to_owned(),
+                "alice".to_owned(),
+                "1".to_owned(),
+                "nyc".to_owned()
+            ]]
+        );
+    }
+
+    #[test]
+    fn join_left_pads_unmatched_left_rows() {
+        let left = HeaderedCSVTable::from_string("id,name\r\n1,alice\r\n2,bob\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("id,city\r\n1,nyc\r\n").unwrap();
+        let joined = left.join(&right, "id", "id", JoinKind::Left);
+        assert_eq!(
+            sorted_rows(&joined),
+            vec![
+                vec!["1".to_owned(), "alice".to_owned(), "1".to_owned(), "nyc".to_owned()],
+                vec!["2".to_owned(), "bob".to_owned(), "".to_owned(), "".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn join_right_pads_unmatched_right_rows() {
+        let left = HeaderedCSVTable::from_string("id,name\r\n1,alice\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("id,city\r\n1,nyc\r\n2,la\r\n").unwrap();
+        let joined = left.join(&right, "id", "id", JoinKind::Right);
+        assert_eq!(
+            sorted_rows(&joined),
+            vec![
+                vec!["".to_owned(), "".to_owned(), "2".to_owned(), "la".to_owned()],
+                vec!["1".to_owned(), "alice".to_owned(), "1".to_owned(), "nyc".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn join_full_pads_both_unmatched_sides() {
+        let left = HeaderedCSVTable::from_string("id,name\r\n1,alice\r\n2,bob\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("id,city\r\n1,nyc\r\n3,la\r\n").unwrap();
+        let joined = left.join(&right, "id", "id", JoinKind::Full);
+        assert_eq!(
+            sorted_rows(&joined),
+            vec![
+                vec!["".to_owned(), "".to_owned(), "3".to_owned(), "la".to_owned()],
+                vec!["1".to_owned(), "alice".to_owned(), "1".to_owned(), "nyc".to_owned()],
+                vec!["2".to_owned(), "bob".to_owned(), "".to_owned(), "".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn join_cross_ignores_keys_and_emits_cartesian_product() {
+        let left = HeaderedCSVTable::from_string("id\r\n1\r\n2\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("letter\r\na\r\nb\r\n").unwrap();
+        let joined = left.join(&right, "id", "letter", JoinKind::Cross);
+        assert_eq!(joined.number_of_rows(), 4);
+        assert_eq!(
+            sorted_rows(&joined),
+            vec![
+                vec!["1".to_owned(), "a".to_owned()],
+                vec!["1".to_owned(), "b".to_owned()],
+                vec!["2".to_owned(), "a".to_owned()],
+                vec!["2".to_owned(), "b".to_owned()],
+            ]
+        );
+    }
+
+    #[test]
+    fn join_does_not_panic_on_duplicate_header_names() {
+        let left = HeaderedCSVTable::from_string("a,a\r\n1,2\r\n").unwrap();
+        let right = HeaderedCSVTable::from_string("a,a\r\n1,3\r\n").unwrap();
+        let joined = left.join(&right, "a", "a", JoinKind::Inner);
+        assert_eq!(joined.number_of_columns(), joined.to_unheadered_csv_input_table().get_row(0).unwrap().len());
+    }
+
+    #[test]
+    fn frequencies_breaks_ties_lexicographically_and_truncates() {
+        let table =
+            HeaderedCSVTable::from_string("fruit\r\nbanana\r\napple\r\nbanana\r\napple\r\ncherry\r\n")
+                .unwrap();
+        assert_eq!(
+            table.frequencies("fruit", None),
+            vec![
+                ("apple".to_owned(), 2),
+                ("banana".to_owned(), 2),
+                ("cherry".to_owned(), 1),
+            ]
+        );
+        assert_eq!(
+            table.frequencies("fruit", Some(1)),
+            vec![("apple".to_owned(), 2)]
+        );
+    }
+
+    #[test]
+    fn describe_reports_numeric_summary_only_when_every_non_empty_cell_parses() {
+        let table = HeaderedCSVTable::from_string("n,label\r\n1,a\r\n2,\r\n3,b\r\n").unwrap();
+        let summaries = table.describe();
+
+        let numeric_column = summaries.iter().find(|s| s.column == "n").unwrap();
+        assert_eq!(numeric_column.count, 3);
+        assert_eq!(numeric_column.empty, 0);
+        assert_eq!(numeric_column.distinct, 3);
+        let numeric = numeric_column.numeric.expect("n is fully numeric");
+        assert_eq!(numeric.min, 1.0);
+        assert_eq!(numeric.max, 3.0);
+        assert_eq!(numeric.mean, 2.0);
+
+        let label_column = summaries.iter().find(|s| s.column == "label").unwrap();
+        assert_eq!(label_column.empty, 1);
+        assert!(label_column.numeric.is_none());
+    }
+
+    #[test]
+    fn describe_does_not_panic_on_duplicate_header_names() {
+        let table = HeaderedCSVTable::from_string("a,a\r\n1,2\r\n").unwrap();
+        let summaries = table.describe();
+        assert_eq!(summaries.len(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Person {
+        id: u32,
+        name: String,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrips_records_through_from_records_and_deserialize_row() {
+        let people = vec![
+            Person { id: 1, name: "alice".to_owned() },
+            Person { id: 2, name: "bob".to_owned() },
+        ];
+        let table = HeaderedCSVTable::from_records(&people).unwrap();
+        assert_eq!(table.number_of_rows(), 2);
+        let first: Person = table.deserialize_row(0).unwrap();
+        let second: Person = table.deserialize_row(1).unwrap();
+        assert_eq!(first, people[0]);
+        assert_eq!(second, people[1]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+    struct Event {
+        timestamp: u32,
+        user: String,
+        action: String,
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn from_records_preserves_struct_declaration_order_in_the_header_row() {
+        let events = vec![Event {
+            timestamp: 100,
+            user: "alice".to_owned(),
+            action: "login".to_owned(),
+        }];
+        let table = HeaderedCSVTable::from_records(&events).unwrap();
+        assert_eq!(
+            table.to_unheadered_csv_input_table().get_row(0).unwrap(),
+            vec!["timestamp".to_owned(), "user".to_owned(), "action".to_owned()]
+        );
+        let roundtripped: Event = table.deserialize_row(0).unwrap();
+        assert_eq!(roundtripped, events[0]);
     }
 }